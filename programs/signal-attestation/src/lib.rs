@@ -1,4 +1,9 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
 
 declare_id!("SigAtt1111111111111111111111111111111111111");
 
@@ -14,10 +19,37 @@ pub enum Classification {
     Time      = 5,
 }
 
+impl TryFrom<u8> for Classification {
+    type Error = ();
+
+    fn try_from(value: u8) -> core::result::Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Classification::Capital),
+            1 => Ok(Classification::Info),
+            2 => Ok(Classification::Velocity),
+            3 => Ok(Classification::Liquidity),
+            4 => Ok(Classification::News),
+            5 => Ok(Classification::Time),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Upper bound for `confidence_bps`: 100.00% expressed in basis points.
+const MAX_CONFIDENCE_BPS: u16 = 10_000;
+
+/// Current `SignalAttestation::schema_version`. Bump when the on-chain
+/// layout or the meaning of an existing field changes.
+const SIGNAL_ATTESTATION_SCHEMA_VERSION: u8 = 1;
+
 /// On-chain attestation account. One PDA per scored signal.
 /// Seeds: ["attestation", authority, movement_id_hash]
 #[account]
+#[derive(InitSpace)]
 pub struct SignalAttestation {
+    /// Layout version, so future scorer schema changes can be
+    /// distinguished by readers without guessing from field values
+    pub schema_version: u8,
     /// SHA-256 of the full signal JSON payload
     pub signal_hash: [u8; 32],
     /// SHA-256 of market_id (keeps PDA seeds fixed-length)
@@ -30,13 +62,13 @@ pub struct SignalAttestation {
     pub timestamp: i64,
     /// The authority (service wallet) that submitted this attestation
     pub authority: Pubkey,
+    /// The scorer signing key whose ed25519 signature over this signal's
+    /// fields was verified at record time
+    pub scorer_pubkey: Pubkey,
     /// Bump seed for PDA derivation
     pub bump: u8,
 }
 
-/// Fixed size: 32 + 32 + 1 + 2 + 8 + 32 + 1 = 108 bytes + 8 discriminator = 116
-const ATTESTATION_SIZE: usize = 8 + 32 + 32 + 1 + 2 + 8 + 32 + 1;
-
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct RecordSignalParams {
     pub signal_hash: [u8; 32],
@@ -45,24 +77,416 @@ pub struct RecordSignalParams {
     pub classification: u8,
     pub confidence_bps: u16,
     pub timestamp: i64,
+    /// Detached ed25519 signature over the canonical serialization of
+    /// `SignedSignalPayload`, checked against the preceding Ed25519
+    /// native program instruction in this transaction and against the
+    /// scorer key registered in `ScorerRegistry`
+    pub signature: [u8; 64],
+}
+
+/// Program-configured registry of the scoring service's signing key.
+/// Singleton PDA; seeds: ["scorer_registry"].
+///
+/// `record_signal` trusts this account (not caller-supplied params) for
+/// the scorer pubkey an attestation's signature is checked against, so a
+/// submitting `authority` cannot attest a signal "scorer-signed" with a
+/// key of its own choosing.
+#[account]
+pub struct ScorerRegistry {
+    /// Key allowed to rotate `scorer_pubkey` via `set_scorer_pubkey`
+    pub admin: Pubkey,
+    /// The scoring service's current signing key
+    pub scorer_pubkey: Pubkey,
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+/// Fixed size: 32 + 32 + 1 = 65 bytes + 8 discriminator = 73
+const SCORER_REGISTRY_SIZE: usize = 8 + 32 + 32 + 1;
+
+/// The subset of `RecordSignalParams` that the scorer actually signs off
+/// on. Kept separate from `RecordSignalParams` because the signature
+/// itself obviously can't be part of its own signed payload.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SignedSignalPayload {
+    pub signal_hash: [u8; 32],
+    pub market_id_hash: [u8; 32],
+    pub movement_id_hash: [u8; 32],
+    pub classification: u8,
+    pub confidence_bps: u16,
+    pub timestamp: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateConfidenceParams {
+    pub confidence_bps: u16,
+    pub timestamp: i64,
+    /// Detached ed25519 signature over the canonical serialization of
+    /// `SignedConfidenceUpdatePayload`, re-checked the same way
+    /// `record_signal` checks `RecordSignalParams.signature` so a
+    /// revision carries the same scorer-authenticity guarantee as the
+    /// original attestation
+    pub signature: [u8; 64],
+}
+
+/// The subset of `UpdateConfidenceParams` the scorer signs off on, bound
+/// to the specific signal (via `signal_hash`, read from the existing
+/// attestation rather than taken as a param) so a signed revision can't
+/// be replayed against a different attestation.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SignedConfidenceUpdatePayload {
+    pub signal_hash: [u8; 32],
+    pub confidence_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Loads the Ed25519 native program instruction that must immediately
+/// precede this one in the same transaction and asserts that it proves a
+/// signature by `expected_pubkey` over `expected_message`, matching
+/// `expected_signature`.
+fn verify_scorer_signature(
+    instructions_sysvar: &AccountInfo,
+    expected_pubkey: &Pubkey,
+    expected_signature: &[u8; 64],
+    expected_message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, ErrorCode::MissingEd25519Instruction);
+
+    let ed25519_ix =
+        load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require_keys_eq!(
+        ed25519_ix.program_id,
+        ed25519_program::ID,
+        ErrorCode::InvalidEd25519Program
+    );
+
+    // Native Ed25519Program instruction data layout: a one-byte signature
+    // count, one padding byte, then one 14-byte offsets struct per
+    // signature (signature_offset, signature_instruction_index,
+    // public_key_offset, public_key_instruction_index,
+    // message_data_offset, message_data_size, message_instruction_index),
+    // followed by the referenced signature/pubkey/message bytes.
+    let data = &ed25519_ix.data;
+    require!(data.len() >= 16, ErrorCode::MalformedEd25519Instruction);
+    require!(data[0] == 1, ErrorCode::MalformedEd25519Instruction);
+
+    let offsets = &data[2..16];
+    let signature_offset = u16::from_le_bytes([offsets[0], offsets[1]]) as usize;
+    let signature_instruction_index = u16::from_le_bytes([offsets[2], offsets[3]]);
+    let public_key_offset = u16::from_le_bytes([offsets[4], offsets[5]]) as usize;
+    let public_key_instruction_index = u16::from_le_bytes([offsets[6], offsets[7]]);
+    let message_data_offset = u16::from_le_bytes([offsets[8], offsets[9]]) as usize;
+    let message_data_size = u16::from_le_bytes([offsets[10], offsets[11]]) as usize;
+    let message_instruction_index = u16::from_le_bytes([offsets[12], offsets[13]]);
+
+    // Each `*_instruction_index` must point at "this instruction"
+    // (u16::MAX is the native program's sentinel for that). Otherwise the
+    // offsets above could be read out of a *different* instruction in the
+    // same transaction than the one the native program actually verified
+    // the signature against, letting a forged payload ride along with a
+    // genuinely valid signature over unrelated data.
+    require!(
+        signature_instruction_index == u16::MAX
+            && public_key_instruction_index == u16::MAX
+            && message_instruction_index == u16::MAX,
+        ErrorCode::Ed25519InstructionIndexMismatch
+    );
+
+    let signature = data
+        .get(signature_offset..signature_offset + 64)
+        .ok_or(ErrorCode::MalformedEd25519Instruction)?;
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ErrorCode::MalformedEd25519Instruction)?;
+    let message = data
+        .get(message_data_offset..message_data_offset + message_data_size)
+        .ok_or(ErrorCode::MalformedEd25519Instruction)?;
+
+    require!(
+        public_key == expected_pubkey.as_ref(),
+        ErrorCode::ScorerPubkeyMismatch
+    );
+    require!(
+        signature == expected_signature,
+        ErrorCode::ScorerSignatureMismatch
+    );
+    require!(message == expected_message, ErrorCode::ScorerMessageMismatch);
+
+    Ok(())
+}
+
+/// On-chain Merkle root attesting to an entire batch of scored signals.
+/// Seeds: ["batch", authority, batch_id]
+#[account]
+pub struct BatchAttestation {
+    /// Root of the Merkle tree built over the batch's leaves
+    pub merkle_root: [u8; 32],
+    /// Number of leaves folded into `merkle_root`
+    pub leaf_count: u32,
+    /// Unix timestamp (seconds) when the batch was committed
+    pub timestamp: i64,
+    /// The authority (service wallet) that submitted this batch
+    pub authority: Pubkey,
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+/// Fixed size: 32 + 4 + 8 + 32 + 1 = 77 bytes + 8 discriminator = 85
+const BATCH_ATTESTATION_SIZE: usize = 8 + 32 + 4 + 8 + 32 + 1;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RecordBatchParams {
+    pub batch_id: u64,
+    pub merkle_root: [u8; 32],
+    pub leaf_count: u32,
+    pub timestamp: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct VerifyMembershipParams {
+    pub signal_hash: [u8; 32],
+    pub market_id_hash: [u8; 32],
+    pub movement_id_hash: [u8; 32],
+    pub classification: u8,
+    pub confidence_bps: u16,
+    pub timestamp: i64,
+    pub leaf_index: u32,
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Emitted whenever `record_signal` attests a new scored signal, so
+/// off-chain indexers can follow an ordered event stream instead of
+/// polling and diffing PDAs.
+#[event]
+pub struct SignalRecorded {
+    pub signal_hash: [u8; 32],
+    pub market_id_hash: [u8; 32],
+    pub movement_id_hash: [u8; 32],
+    pub classification: u8,
+    pub confidence_bps: u16,
+    pub timestamp: i64,
+    pub authority: Pubkey,
+    pub slot: u64,
+}
+
+/// Emitted whenever `record_batch` commits a new Merkle root.
+#[event]
+pub struct BatchRecorded {
+    pub batch_id: u64,
+    pub merkle_root: [u8; 32],
+    pub leaf_count: u32,
+    pub timestamp: i64,
+    pub authority: Pubkey,
+    pub slot: u64,
+}
+
+/// Emitted whenever `update_confidence` revises a recorded signal.
+#[event]
+pub struct ConfidenceUpdated {
+    pub attestation: Pubkey,
+    pub confidence_bps: u16,
+    pub timestamp: i64,
+    pub authority: Pubkey,
+    pub slot: u64,
+}
+
+/// Hashes the fields of a single scored signal into its Merkle leaf.
+fn leaf_hash(
+    signal_hash: &[u8; 32],
+    market_id_hash: &[u8; 32],
+    movement_id_hash: &[u8; 32],
+    classification: u8,
+    confidence_bps: u16,
+    timestamp: i64,
+) -> [u8; 32] {
+    hashv(&[
+        signal_hash,
+        market_id_hash,
+        movement_id_hash,
+        &[classification],
+        &confidence_bps.to_le_bytes(),
+        &timestamp.to_le_bytes(),
+    ])
+    .to_bytes()
 }
 
 #[program]
 pub mod signal_attestation {
     use super::*;
 
+    /// Creates the singleton `ScorerRegistry`, binding `scorer_pubkey` as
+    /// the only key `record_signal` will accept an Ed25519 signature
+    /// from. Callable once, by whoever pays for the account.
+    pub fn init_scorer_registry(
+        ctx: Context<InitScorerRegistry>,
+        scorer_pubkey: Pubkey,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.scorer_registry;
+        registry.admin = ctx.accounts.admin.key();
+        registry.scorer_pubkey = scorer_pubkey;
+        registry.bump = ctx.bumps.scorer_registry;
+        Ok(())
+    }
+
+    /// Rotates the registered scorer signing key. Admin-only.
+    pub fn set_scorer_pubkey(ctx: Context<SetScorerPubkey>, scorer_pubkey: Pubkey) -> Result<()> {
+        ctx.accounts.scorer_registry.scorer_pubkey = scorer_pubkey;
+        Ok(())
+    }
+
     pub fn record_signal(
         ctx: Context<RecordSignal>,
         params: RecordSignalParams,
     ) -> Result<()> {
+        Classification::try_from(params.classification)
+            .map_err(|_| error!(ErrorCode::InvalidClassification))?;
+        require!(
+            params.confidence_bps <= MAX_CONFIDENCE_BPS,
+            ErrorCode::ConfidenceOutOfRange
+        );
+
+        let payload = SignedSignalPayload {
+            signal_hash: params.signal_hash,
+            market_id_hash: params.market_id_hash,
+            movement_id_hash: params.movement_id_hash,
+            classification: params.classification,
+            confidence_bps: params.confidence_bps,
+            timestamp: params.timestamp,
+        };
+        let scorer_pubkey = ctx.accounts.scorer_registry.scorer_pubkey;
+        verify_scorer_signature(
+            &ctx.accounts.instructions,
+            &scorer_pubkey,
+            &params.signature,
+            &payload.try_to_vec()?,
+        )?;
+
         let attestation = &mut ctx.accounts.attestation;
+        attestation.schema_version = SIGNAL_ATTESTATION_SCHEMA_VERSION;
         attestation.signal_hash = params.signal_hash;
         attestation.market_id_hash = params.market_id_hash;
         attestation.classification = params.classification;
         attestation.confidence_bps = params.confidence_bps;
         attestation.timestamp = params.timestamp;
         attestation.authority = ctx.accounts.authority.key();
+        attestation.scorer_pubkey = scorer_pubkey;
         attestation.bump = ctx.bumps.attestation;
+
+        emit!(SignalRecorded {
+            signal_hash: params.signal_hash,
+            market_id_hash: params.market_id_hash,
+            movement_id_hash: params.movement_id_hash,
+            classification: params.classification,
+            confidence_bps: params.confidence_bps,
+            timestamp: params.timestamp,
+            authority: attestation.authority,
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+
+    /// Lets the scorer revise a previously recorded signal's confidence
+    /// (e.g. after a re-score) without creating a new account. Requires a
+    /// fresh Ed25519 signature from the registered scorer key, the same
+    /// way `record_signal` does, so a revision can't silently void the
+    /// attestation's scorer-authenticity guarantee.
+    pub fn update_confidence(
+        ctx: Context<UpdateConfidence>,
+        params: UpdateConfidenceParams,
+    ) -> Result<()> {
+        require!(
+            params.confidence_bps <= MAX_CONFIDENCE_BPS,
+            ErrorCode::ConfidenceOutOfRange
+        );
+
+        let payload = SignedConfidenceUpdatePayload {
+            signal_hash: ctx.accounts.attestation.signal_hash,
+            confidence_bps: params.confidence_bps,
+            timestamp: params.timestamp,
+        };
+        verify_scorer_signature(
+            &ctx.accounts.instructions,
+            &ctx.accounts.scorer_registry.scorer_pubkey,
+            &params.signature,
+            &payload.try_to_vec()?,
+        )?;
+
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.confidence_bps = params.confidence_bps;
+        attestation.timestamp = params.timestamp;
+
+        emit!(ConfidenceUpdated {
+            attestation: attestation.key(),
+            confidence_bps: params.confidence_bps,
+            timestamp: params.timestamp,
+            authority: attestation.authority,
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+
+    /// Closes an attestation, returning its rent-exempt deposit to the
+    /// authority that created it.
+    pub fn close_attestation(_ctx: Context<CloseAttestation>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Commits a whole batch of scored signals as a single Merkle root:
+    /// one account and one transaction instead of one per signal, with
+    /// each signal still provable via `verify_membership`.
+    pub fn record_batch(ctx: Context<RecordBatch>, params: RecordBatchParams) -> Result<()> {
+        let batch = &mut ctx.accounts.batch_attestation;
+        batch.merkle_root = params.merkle_root;
+        batch.leaf_count = params.leaf_count;
+        batch.timestamp = params.timestamp;
+        batch.authority = ctx.accounts.authority.key();
+        batch.bump = ctx.bumps.batch_attestation;
+
+        emit!(BatchRecorded {
+            batch_id: params.batch_id,
+            merkle_root: params.merkle_root,
+            leaf_count: params.leaf_count,
+            timestamp: params.timestamp,
+            authority: batch.authority,
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+
+    /// Recomputes the Merkle root for a single signal's leaf against its
+    /// sibling proof and asserts it matches the committed batch root.
+    pub fn verify_membership(
+        ctx: Context<VerifyMembership>,
+        params: VerifyMembershipParams,
+    ) -> Result<()> {
+        let mut current = leaf_hash(
+            &params.signal_hash,
+            &params.market_id_hash,
+            &params.movement_id_hash,
+            params.classification,
+            params.confidence_bps,
+            params.timestamp,
+        );
+
+        let mut index = params.leaf_index;
+        for sibling in params.proof.iter() {
+            current = if index & 1 == 0 {
+                hashv(&[&current, sibling]).to_bytes()
+            } else {
+                hashv(&[sibling, &current]).to_bytes()
+            };
+            index >>= 1;
+        }
+
+        require!(
+            current == ctx.accounts.batch_attestation.merkle_root,
+            ErrorCode::MerkleProofInvalid
+        );
+
         Ok(())
     }
 }
@@ -73,7 +497,7 @@ pub struct RecordSignal<'info> {
     #[account(
         init,
         payer = authority,
-        space = ATTESTATION_SIZE,
+        space = 8 + SignalAttestation::INIT_SPACE,
         seeds = [
             b"attestation",
             authority.key().as_ref(),
@@ -86,5 +510,121 @@ pub struct RecordSignal<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    #[account(seeds = [b"scorer_registry"], bump = scorer_registry.bump)]
+    pub scorer_registry: Account<'info, ScorerRegistry>,
+
+    /// CHECK: address-constrained to the instructions sysvar; read via
+    /// `load_instruction_at_checked` to introspect the preceding
+    /// Ed25519Program instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitScorerRegistry<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = SCORER_REGISTRY_SIZE,
+        seeds = [b"scorer_registry"],
+        bump,
+    )]
+    pub scorer_registry: Account<'info, ScorerRegistry>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetScorerPubkey<'info> {
+    #[account(
+        mut,
+        seeds = [b"scorer_registry"],
+        bump = scorer_registry.bump,
+        has_one = admin,
+    )]
+    pub scorer_registry: Account<'info, ScorerRegistry>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfidence<'info> {
+    #[account(mut, has_one = authority)]
+    pub attestation: Account<'info, SignalAttestation>,
+
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"scorer_registry"], bump = scorer_registry.bump)]
+    pub scorer_registry: Account<'info, ScorerRegistry>,
+
+    /// CHECK: address-constrained to the instructions sysvar; read via
+    /// `load_instruction_at_checked` to introspect the preceding
+    /// Ed25519Program instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAttestation<'info> {
+    #[account(mut, has_one = authority, close = authority)]
+    pub attestation: Account<'info, SignalAttestation>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: RecordBatchParams)]
+pub struct RecordBatch<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = BATCH_ATTESTATION_SIZE,
+        seeds = [
+            b"batch",
+            authority.key().as_ref(),
+            &params.batch_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub batch_attestation: Account<'info, BatchAttestation>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
     pub system_program: Program<'info, System>,
 }
+
+#[derive(Accounts)]
+pub struct VerifyMembership<'info> {
+    pub batch_attestation: Account<'info, BatchAttestation>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Recomputed Merkle root does not match the committed batch root")]
+    MerkleProofInvalid,
+    #[msg("No Ed25519Program instruction precedes this one in the transaction")]
+    MissingEd25519Instruction,
+    #[msg("Instruction preceding this one is not the Ed25519Program")]
+    InvalidEd25519Program,
+    #[msg("Ed25519Program instruction data is malformed")]
+    MalformedEd25519Instruction,
+    #[msg("Ed25519 instruction public key does not match the declared scorer")]
+    ScorerPubkeyMismatch,
+    #[msg("Ed25519 instruction signature does not match the declared signature")]
+    ScorerSignatureMismatch,
+    #[msg("Ed25519 instruction message does not match the signal's canonical payload")]
+    ScorerMessageMismatch,
+    #[msg("Ed25519 instruction offsets do not reference this instruction's own data")]
+    Ed25519InstructionIndexMismatch,
+    #[msg("classification byte does not match a known Classification variant")]
+    InvalidClassification,
+    #[msg("confidence_bps exceeds 10_000 (100.00%)")]
+    ConfidenceOutOfRange,
+}